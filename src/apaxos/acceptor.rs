@@ -8,6 +8,7 @@ use crate::apaxos::accepted::Accepted;
 use crate::apaxos::greater_equal::GreaterEqual;
 use crate::apaxos::greater_equal_map::Map;
 use crate::apaxos::proposal::Proposal;
+use crate::apaxos::storage::Storage;
 use crate::Types;
 
 #[derive(Clone)]
@@ -36,6 +37,22 @@ impl<T: Types> Validate for Acceptor<T> {
     }
 }
 
+/// The reply to a phase-1 request, carrying everything a [`Proposer`] needs
+/// to implement the value-adoption rule.
+#[derive(Clone)]
+pub struct Phase1Reply<T: Types> {
+    /// The highest-time value this [`Acceptor`] has already accepted, if
+    /// any. A [`Proposer`] that collects a quorum of replies must adopt the
+    /// one whose `accept_time` is the maximal one under [`GreaterEqual`].
+    pub accepted: Option<Accepted<T>>,
+}
+
+impl<T: Types> Debug for Phase1Reply<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Phase1Reply").field("accepted", &self.accepted).finish()
+    }
+}
+
 impl<T: Types> Debug for Acceptor<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Acceptor").field("store", &self.store).finish()
@@ -55,28 +72,69 @@ where T::Time: std::hash::Hash
 }
 
 impl<T: Types> Acceptor<T> {
+    /// Rebuild an [`Acceptor`] from its durably persisted `time` and
+    /// `accepted` value.
+    ///
+    /// This is the counterpart of flushing to [`Storage`] in
+    /// [`handle_phase1_request`](Self::handle_phase1_request) and
+    /// [`handle_phase2_request`](Self::handle_phase2_request), used to
+    /// recover an [`Acceptor`] on startup after a crash. The rebuilt
+    /// [`Acceptor`] preserves the [`validate()`](Validate::validate)
+    /// invariant that `time.greater_equal(&accepted.accept_time)`, because
+    /// that is the invariant [`Storage`] is required to have persisted.
+    pub(crate) async fn load<S: Storage<T>>(instance: &T::InstanceId, storage: &S) -> Result<Self, S::Error>
+    where T::Time: std::hash::Hash {
+        let (time, accepted) = storage.load(instance).await?;
+
+        Ok(Self {
+            store: Map::new(),
+            time,
+            accepted,
+        })
+    }
+
     /// Handle the phase-1 request from a [`Proposer`], i.e., set up a new
     /// [`Time`] point.
     ///
-    /// Returns the `Time` before handling the request and the updated
-    /// [`Acceptor`] itself.
+    /// Returns the `Time` before handling the request and a
+    /// [`Phase1Reply`] describing what this [`Acceptor`] has already
+    /// accepted, so the [`Proposer`] can implement the value-adoption rule:
+    /// a [`Proposer`] collects a quorum of such replies and must re-propose
+    /// the [`Accepted`] value whose `accept_time` is the maximal one under
+    /// [`GreaterEqual`], falling back to its own value only if no quorum
+    /// member has accepted anything yet.
     ///
     /// The returned `Time` will be used to revert the `Time` if the
     /// [`Proposer`] decide to cancel this round of consensus algorithm.
     /// For example, **2PC** will revert the `Time` if the coordinator receives
     /// conflicting votes(otherwise other [`Proposer`] can not proceed). But
     /// **Classic Paxos** does not have to revert the `Time` but it could.
-    pub(crate) fn handle_phase1_request(&mut self, now: T::Time) -> (T::Time, Self) {
+    ///
+    /// The updated `time` is flushed to `storage` before this returns.
+    pub(crate) async fn handle_phase1_request<S: Storage<T>>(
+        &mut self,
+        instance: &T::InstanceId,
+        now: T::Time,
+        storage: &mut S,
+    ) -> Result<(T::Time, Phase1Reply<T>), S::Error>
+    where T::InstanceId: Clone {
         dbg!("handle_phase1_request", now, self.time);
         dbg!(now.greater_equal(&self.time));
 
-        let now = self.time;
+        let prev = self.time;
+        let new_time = if now.greater_equal(&self.time) { now } else { self.time };
 
-        if now.greater_equal(&self.time) {
-            self.time = now;
-        }
+        // Persist before mutating in-memory state: if this errors, `self`
+        // must stay exactly as durable, or a later request could be
+        // compared against a promise that was never actually made durable.
+        storage.persist_time(instance.clone(), new_time).await?;
+        self.time = new_time;
+
+        let reply = Phase1Reply {
+            accepted: self.accepted.clone(),
+        };
 
-        (now, self.clone())
+        Ok((prev, reply))
     }
 
     /// Revert the `Time` to a previous one if it is still the same
@@ -97,22 +155,40 @@ impl<T: Types> Acceptor<T> {
         }
     }
 
-    pub(crate) fn handle_phase2_request(
+    /// Handle the phase-2 (accept) request from a [`Proposer`].
+    ///
+    /// The updated `time` and `accepted` value are flushed to `storage`
+    /// before this returns.
+    pub(crate) async fn handle_phase2_request<S: Storage<T>>(
         &mut self,
+        instance: &T::InstanceId,
         t: T::Time,
         proposal: Proposal<T, T::Part>,
-    ) -> bool {
+        storage: &mut S,
+    ) -> Result<bool, S::Error>
+    where T::InstanceId: Clone {
         dbg!("handle_phase2_request", t);
         if t.greater_equal(&self.time) {
-            self.time = t;
-            self.accepted = Some(Accepted {
+            let accepted = Accepted {
                 accept_time: t,
                 proposal,
-            });
+            };
+
+            // Persist `time` first and advance it in memory as soon as it's
+            // durable, even if `persist_accepted` below then fails: storage
+            // already holds the higher time at that point, so leaving
+            // `self.time` at its old value would put the in-memory acceptor
+            // *behind* what's durable, letting it wrongly promise/accept a
+            // time that storage has already moved past.
+            storage.persist_time(instance.clone(), t).await?;
+            self.time = t;
 
-            true
+            storage.persist_accepted(instance.clone(), accepted.clone()).await?;
+            self.accepted = Some(accepted);
+
+            Ok(true)
         } else {
-            false
+            Ok(false)
         }
     }
 }
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::apaxos::accepted::Accepted;
+use crate::apaxos::quorum::Quorum;
+use crate::Types;
+
+/// A delegate notified exactly once when a [`Learner`] detects that a value
+/// has been chosen, i.e., accepted by a quorum of [`Acceptor`]s.
+///
+/// This is the `paxos_commit` upcall: it lets a higher layer, such as a
+/// state machine, react to consensus completion instead of polling
+/// [`Acceptor`] internals.
+pub trait PaxosCommit<T: Types> {
+    /// Called exactly once per decided value, with the [`Accepted`] the
+    /// quorum agreed on.
+    fn paxos_commit(&mut self, accepted: Accepted<T>);
+}
+
+/// Collects phase-2 accept acknowledgements from a set of [`Acceptor`]s and
+/// fires [`PaxosCommit::paxos_commit`] exactly once, as soon as a quorum has
+/// accepted the same value.
+///
+/// Votes are grouped by `accept_time` rather than by the accepted value
+/// itself: Paxos safety guarantees that at most one value can be accepted
+/// at a given `accept_time`, so `accept_time` alone is enough to identify a
+/// round without requiring the proposed value to be `Eq + Hash`.
+pub struct Learner<T: Types, Q, C> {
+    /// The set of acceptors that acked each `accept_time` so far.
+    votes: HashMap<T::Time, HashSet<T::NodeId>>,
+
+    /// The accepted value seen for each `accept_time`, kept alongside
+    /// `votes` so it can be handed to `commit` once a quorum is reached.
+    values: HashMap<T::Time, Accepted<T>>,
+
+    /// The [`Quorum`] definition deciding how many acks make a value
+    /// decided.
+    quorum: Q,
+
+    /// Whether a value has already been committed; once `true`,
+    /// `paxos_commit` will not fire again.
+    decided: bool,
+
+    /// The upcall fired exactly once when a value is decided.
+    commit: C,
+}
+
+impl<T: Types, Q: Quorum<T>, C: PaxosCommit<T>> Learner<T, Q, C>
+where
+    T::Time: Eq + Hash,
+    T::NodeId: Eq + Hash,
+{
+    pub fn new(quorum: Q, commit: C) -> Self {
+        Self {
+            votes: HashMap::new(),
+            values: HashMap::new(),
+            quorum,
+            decided: false,
+            commit,
+        }
+    }
+
+    /// Record that `acceptor` accepted `accepted` in a phase-2 response,
+    /// firing [`PaxosCommit::paxos_commit`] once the [`Quorum`] considers
+    /// the acceptors that accepted it a winning phase-2 quorum.
+    pub fn on_phase2_ack(&mut self, acceptor: T::NodeId, accepted: Accepted<T>) {
+        if self.decided {
+            return;
+        }
+
+        let voters = self.votes.entry(accepted.accept_time).or_default();
+        voters.insert(acceptor);
+        self.values.entry(accepted.accept_time).or_insert(accepted.clone());
+
+        if self.quorum.is_phase2_quorum(voters.len()) {
+            self.decided = true;
+            let accepted = self.values.remove(&accepted.accept_time).expect("just inserted above");
+            self.commit.paxos_commit(accepted);
+        }
+    }
+
+    /// Whether this [`Learner`] has already fired [`PaxosCommit::paxos_commit`].
+    pub fn is_decided(&self) -> bool {
+        self.decided
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::apaxos::accepted::Accepted;
+    use crate::apaxos::learner::Learner;
+    use crate::apaxos::learner::PaxosCommit;
+    use crate::apaxos::proposal::Proposal;
+    use crate::apaxos::quorum::Majority;
+    use crate::apaxos::storage::test_fixtures::TestTime;
+    use crate::apaxos::storage::test_fixtures::TestTypes;
+
+    #[derive(Default)]
+    struct CountingCommit {
+        commits: Vec<Accepted<TestTypes>>,
+    }
+
+    impl PaxosCommit<TestTypes> for CountingCommit {
+        fn paxos_commit(&mut self, accepted: Accepted<TestTypes>) {
+            self.commits.push(accepted);
+        }
+    }
+
+    fn accepted(value: u64) -> Accepted<TestTypes> {
+        Accepted {
+            accept_time: TestTime(1),
+            proposal: Proposal::new(value),
+        }
+    }
+
+    #[test]
+    fn test_paxos_commit_fires_exactly_once_and_ignores_later_acks() {
+        let quorum = Majority { cluster_size: 3 };
+        let mut learner = Learner::<TestTypes, _, _>::new(quorum, CountingCommit::default());
+
+        learner.on_phase2_ack(1, accepted(42));
+        assert!(!learner.is_decided());
+
+        // The second ack reaches a majority of 3 and must fire the commit.
+        learner.on_phase2_ack(2, accepted(42));
+        assert!(learner.is_decided());
+        assert_eq!(learner.commit.commits.len(), 1);
+
+        // A third, post-decision ack must not fire `paxos_commit` again.
+        learner.on_phase2_ack(3, accepted(42));
+        assert_eq!(learner.commit.commits.len(), 1);
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::apaxos::acceptor::Acceptor;
+use crate::apaxos::acceptor::Phase1Reply;
+use crate::apaxos::proposal::Proposal;
+use crate::apaxos::storage::Storage;
+use crate::Types;
+
+/// The outcome of running phase-1 against a [`Log`] instance.
+pub enum Phase1Outcome<T: Types> {
+    /// The instance is still open; carries the normal phase-1 reply from
+    /// the per-instance [`Acceptor`], exactly as
+    /// [`Acceptor::handle_phase1_request`] would.
+    Open(T::Time, Phase1Reply<T>),
+
+    /// This instance has already been decided. A lagging [`Proposer`]
+    /// should commit `proposal` locally instead of running consensus for
+    /// this instance, and move on to the next open one.
+    OldInstance(Proposal<T, T::Part>),
+}
+
+/// A replicated log: a collection of per-instance [`Acceptor`]s indexed by
+/// [`Types::InstanceId`], so a single [`Log`] can agree on a sequence of
+/// values instead of just one, mirroring Multi-Paxos.
+///
+/// When a [`Proposer`] runs phase-1 for an instance this [`Log`] already
+/// knows to be decided, [`Log::handle_phase1_request`] replies with
+/// [`Phase1Outcome::OldInstance`] instead of running consensus again, so the
+/// lagging [`Proposer`] can catch up by committing the returned value
+/// locally.
+pub struct Log<T: Types> {
+    /// Acceptors for instances that are still open, i.e., not yet decided.
+    instances: HashMap<T::InstanceId, Acceptor<T>>,
+
+    /// The decided value of every instance already known to be committed,
+    /// used to answer "old instance" phase-1 requests.
+    committed: HashMap<T::InstanceId, Proposal<T, T::Part>>,
+}
+
+impl<T: Types> Default for Log<T>
+where T::InstanceId: Eq + Hash
+{
+    fn default() -> Self {
+        Self {
+            instances: HashMap::new(),
+            committed: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Types> Log<T>
+where
+    T::InstanceId: Eq + Hash + Clone,
+    T::Time: Hash,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the open [`Acceptor`] for `instance`, reconstructing it from
+    /// `storage` via [`Acceptor::load`] the first time `instance` is
+    /// touched in this process instead of defaulting it to empty state —
+    /// otherwise the durability [`Storage`] provides would never actually
+    /// reach the multi-instance layer across a restart.
+    async fn acceptor_mut<'a, S: Storage<T>>(
+        &'a mut self,
+        instance: &T::InstanceId,
+        storage: &S,
+    ) -> Result<&'a mut Acceptor<T>, S::Error> {
+        if !self.instances.contains_key(instance) {
+            let acceptor = Acceptor::load(instance, storage).await?;
+            self.instances.insert(instance.clone(), acceptor);
+        }
+
+        Ok(self.instances.get_mut(instance).expect("just inserted above"))
+    }
+
+    /// Handle a phase-1 request for `instance`.
+    ///
+    /// Routes to the per-instance [`Acceptor`], creating one if `instance`
+    /// has not been seen before, unless `instance` is already committed, in
+    /// which case the committed value is returned directly so the caller
+    /// can catch up without re-running consensus.
+    pub(crate) async fn handle_phase1_request<S: Storage<T>>(
+        &mut self,
+        instance: T::InstanceId,
+        now: T::Time,
+        storage: &mut S,
+    ) -> Result<Phase1Outcome<T>, S::Error> {
+        if let Some(proposal) = self.committed.get(&instance) {
+            return Ok(Phase1Outcome::OldInstance(proposal.clone()));
+        }
+
+        let acceptor = self.acceptor_mut(&instance, &*storage).await?;
+        let (prev, reply) = acceptor.handle_phase1_request(&instance, now, storage).await?;
+
+        Ok(Phase1Outcome::Open(prev, reply))
+    }
+
+    /// Handle a phase-2 (accept) request for `instance`.
+    ///
+    /// Like [`Log::handle_phase1_request`], this is a no-op returning
+    /// `false` for an `instance` that is already committed: a [`Proposer`]
+    /// lagging that far behind must catch up via
+    /// [`Log::committed_value`] instead of completing phase-2.
+    pub(crate) async fn handle_phase2_request<S: Storage<T>>(
+        &mut self,
+        instance: T::InstanceId,
+        t: T::Time,
+        proposal: Proposal<T, T::Part>,
+        storage: &mut S,
+    ) -> Result<bool, S::Error> {
+        if self.committed.contains_key(&instance) {
+            return Ok(false);
+        }
+
+        let acceptor = self.acceptor_mut(&instance, &*storage).await?;
+        acceptor.handle_phase2_request(&instance, t, proposal, storage).await
+    }
+
+    /// Mark `instance` as committed with `proposal`.
+    ///
+    /// This drops the instance's in-progress [`Acceptor`] and records the
+    /// decided value, so future phase-1/phase-2 requests for `instance`
+    /// short-circuit to the committed value instead of running consensus
+    /// again.
+    pub(crate) fn commit(&mut self, instance: T::InstanceId, proposal: Proposal<T, T::Part>) {
+        self.instances.remove(&instance);
+        self.committed.insert(instance, proposal);
+    }
+
+    /// Look up the committed value of `instance`, if it has been decided.
+    pub(crate) fn committed_value(&self, instance: &T::InstanceId) -> Option<&Proposal<T, T::Part>> {
+        self.committed.get(instance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+
+    use crate::apaxos::accepted::Accepted;
+    use crate::apaxos::log::Log;
+    use crate::apaxos::log::Phase1Outcome;
+    use crate::apaxos::proposal::Proposal;
+    use crate::apaxos::storage::test_fixtures::TestTime;
+    use crate::apaxos::storage::test_fixtures::TestTypes;
+    use crate::apaxos::storage::Storage;
+
+    /// An in-memory [`Storage`] keyed by instance, standing in for a real
+    /// log/blob store in tests.
+    #[derive(Default)]
+    struct InMemoryStorage {
+        times: HashMap<u64, TestTime>,
+        accepted: HashMap<u64, Accepted<TestTypes>>,
+    }
+
+    impl Storage<TestTypes> for InMemoryStorage {
+        type Error = Infallible;
+
+        async fn persist_time(&mut self, instance: u64, time: TestTime) -> Result<(), Infallible> {
+            self.times.insert(instance, time);
+            Ok(())
+        }
+
+        async fn persist_accepted(&mut self, instance: u64, accepted: Accepted<TestTypes>) -> Result<(), Infallible> {
+            self.accepted.insert(instance, accepted);
+            Ok(())
+        }
+
+        async fn load(&self, instance: &u64) -> Result<(TestTime, Option<Accepted<TestTypes>>), Infallible> {
+            Ok((
+                self.times.get(instance).copied().unwrap_or_default(),
+                self.accepted.get(instance).cloned(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_two_concurrent_instances_do_not_share_storage_records() {
+        let mut log = Log::<TestTypes>::new();
+        let mut storage = InMemoryStorage::default();
+
+        log.handle_phase1_request(1, TestTime(5), &mut storage).await.unwrap();
+        log.handle_phase1_request(2, TestTime(9), &mut storage).await.unwrap();
+
+        // Each instance's own `time` must have been persisted under its own
+        // key, not overwritten by the other instance's record.
+        assert_eq!(storage.times.get(&1), Some(&TestTime(5)));
+        assert_eq!(storage.times.get(&2), Some(&TestTime(9)));
+    }
+
+    #[tokio::test]
+    async fn test_committed_instance_short_circuits_to_old_instance() {
+        let mut log = Log::<TestTypes>::new();
+        let mut storage = InMemoryStorage::default();
+
+        log.handle_phase1_request(1, TestTime(5), &mut storage).await.unwrap();
+        log.commit(1, Proposal::new(42u64));
+
+        // A later phase-1 for the same instance must not run consensus
+        // again; it must short-circuit to the value already committed.
+        let outcome = log.handle_phase1_request(1, TestTime(9), &mut storage).await.unwrap();
+        match outcome {
+            Phase1Outcome::OldInstance(proposal) => assert_eq!(proposal, Proposal::new(42u64)),
+            Phase1Outcome::Open(..) => panic!("expected OldInstance, got Open"),
+        }
+
+        // Likewise for phase-2: no vote should be cast for an already
+        // decided instance.
+        let accepted = log
+            .handle_phase2_request(1, TestTime(9), Proposal::new(99u64), &mut storage)
+            .await
+            .unwrap();
+        assert!(!accepted);
+        assert_eq!(log.committed_value(&1), Some(&Proposal::new(42u64)));
+    }
+}
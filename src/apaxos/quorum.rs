@@ -0,0 +1,166 @@
+use std::hash::Hash;
+
+use crate::apaxos::acceptor::Phase1Reply;
+use crate::apaxos::greater_equal::GreaterEqual;
+use crate::apaxos::greater_equal_map::Map;
+use crate::apaxos::proposal::Proposal;
+use crate::Types;
+
+/// Decides whether a set of [`Acceptor`](crate::apaxos::acceptor::Acceptor)
+/// responses constitutes a winning quorum.
+///
+/// Grid, weighted, and flexible quorums (distinct phase-1/phase-2 sizes)
+/// can all implement this, as long as every phase-1 quorum intersects
+/// every phase-2 quorum.
+pub trait Quorum<T: Types> {
+    /// Whether `count` phase-1 (prepare) responses are enough to proceed to
+    /// phase-2.
+    fn is_phase1_quorum(&self, count: usize) -> bool;
+
+    /// Whether `count` phase-2 (accept) responses are enough to consider a
+    /// value chosen.
+    fn is_phase2_quorum(&self, count: usize) -> bool;
+}
+
+/// The default [`Quorum`]: a simple majority of a cluster of `cluster_size`
+/// acceptors, used for both phase-1 and phase-2.
+pub struct Majority {
+    pub cluster_size: usize,
+}
+
+impl<T: Types> Quorum<T> for Majority {
+    fn is_phase1_quorum(&self, count: usize) -> bool {
+        count * 2 > self.cluster_size
+    }
+
+    fn is_phase2_quorum(&self, count: usize) -> bool {
+        count * 2 > self.cluster_size
+    }
+}
+
+/// Reduce a quorum of [`Phase1Reply`] to the [`Proposal`] a [`Proposer`]
+/// must re-propose in phase-2.
+///
+/// Implements the value-adoption rule: among the replies' accepted values,
+/// pick the one whose `accept_time` is highest; fall back to `own_proposal`
+/// if no reply has accepted anything yet.
+///
+/// [`Map::maximals`] can return several mutually-incomparable `accept_time`s
+/// under [`GreaterEqual`] (see `greater_equal_map`'s own tests), and picking
+/// whichever one a `HashMap` iterator happens to yield first would make
+/// value-adoption depend on process-local hash iteration order — exactly
+/// the kind of nondeterminism that breaks the safety value-adoption is
+/// supposed to provide. `T::Time` is required to additionally implement
+/// `Ord` here so ties are always broken the same way regardless of
+/// iteration order; see [`highest`] for the tie-break itself.
+///
+/// Returns `None` if `replies` does not yet constitute a phase-1 quorum
+/// under `quorum`.
+pub fn adopt_value<T: Types>(
+    replies: &[Phase1Reply<T>],
+    quorum: &impl Quorum<T>,
+    own_proposal: Proposal<T, T::Part>,
+) -> Option<Proposal<T, T::Part>>
+where T::Time: Eq + Hash + Ord + Clone {
+    if !quorum.is_phase1_quorum(replies.len()) {
+        return None;
+    }
+
+    let mut by_time = Map::new();
+    for reply in replies {
+        if let Some(accepted) = &reply.accepted {
+            by_time.insert(accepted.accept_time, accepted.proposal.clone());
+        }
+    }
+
+    if let Some(proposal) = highest(&by_time) {
+        return Some(proposal.clone());
+    }
+
+    Some(own_proposal)
+}
+
+/// Deterministically pick the value of the maximal-under-[`GreaterEqual`]
+/// key with the highest `Ord` among `by_time`'s maximals, so the choice
+/// does not depend on `HashMap`'s unspecified iteration order when more
+/// than one key is maximal at once.
+fn highest<K, V>(by_time: &Map<K, V>) -> Option<&V>
+where K: GreaterEqual + Eq + Hash + Ord + Clone {
+    by_time.maximals().max_by_key(|(time, _)| (*time).clone()).map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::apaxos::accepted::Accepted;
+    use crate::apaxos::acceptor::Phase1Reply;
+    use crate::apaxos::greater_equal::GreaterEqual;
+    use crate::apaxos::greater_equal_map::Map;
+    use crate::apaxos::proposal::Proposal;
+    use crate::apaxos::quorum::adopt_value;
+    use crate::apaxos::quorum::highest;
+    use crate::apaxos::quorum::Majority;
+    use crate::apaxos::storage::test_fixtures::TestTime;
+    use crate::apaxos::storage::test_fixtures::TestTypes;
+
+    fn reply_with(accept_time: u64, value: u64) -> Phase1Reply<TestTypes> {
+        Phase1Reply {
+            accepted: Some(Accepted {
+                accept_time: TestTime(accept_time),
+                proposal: Proposal::new(value),
+            }),
+        }
+    }
+
+    fn empty_reply() -> Phase1Reply<TestTypes> {
+        Phase1Reply { accepted: None }
+    }
+
+    #[test]
+    fn test_adopt_value_returns_none_without_a_quorum() {
+        let quorum = Majority { cluster_size: 3 };
+        let replies = [reply_with(1, 10)];
+
+        assert!(adopt_value(&replies, &quorum, Proposal::new(99u64)).is_none());
+    }
+
+    #[test]
+    fn test_adopt_value_falls_back_to_own_proposal_when_nothing_accepted() {
+        let quorum = Majority { cluster_size: 3 };
+        let replies = [empty_reply(), empty_reply()];
+
+        assert_eq!(adopt_value(&replies, &quorum, Proposal::new(99u64)), Some(Proposal::new(99u64)));
+    }
+
+    #[test]
+    fn test_adopt_value_picks_the_reply_with_the_highest_accept_time() {
+        let quorum = Majority { cluster_size: 3 };
+        let replies = [reply_with(1, 10), reply_with(5, 50)];
+
+        assert_eq!(adopt_value(&replies, &quorum, Proposal::new(99u64)), Some(Proposal::new(50u64)));
+    }
+
+    /// A `Time`-like key whose [`GreaterEqual`] relation (modulo, same as
+    /// `greater_equal_map`'s own `P`) leaves `6` and `9` incomparable, but
+    /// which also has a total `Ord` so ties can be broken deterministically.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    struct P(u64);
+
+    impl GreaterEqual for P {
+        fn greater_equal(&self, other: &Self) -> bool {
+            self.0 % other.0 == 0
+        }
+    }
+
+    #[test]
+    fn test_highest_breaks_incomparable_maximals_deterministically() {
+        let mut by_time = Map::new();
+        by_time.insert(P(6), "six");
+        by_time.insert(P(9), "nine");
+
+        // P(6) and P(9) are incomparable under `greater_equal`, so both are
+        // maximal; `highest` must still deterministically pick the one with
+        // the greater `Ord` value instead of depending on HashMap iteration
+        // order.
+        assert_eq!(highest(&by_time), Some(&"nine"));
+    }
+}
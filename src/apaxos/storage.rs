@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::future::Future;
+
+use crate::apaxos::accepted::Accepted;
+use crate::Types;
+
+#[cfg(test)]
+#[path = "test_fixtures.rs"]
+pub(crate) mod test_fixtures;
+
+/// Durable storage backing an [`Acceptor`](crate::apaxos::acceptor::Acceptor).
+///
+/// An in-memory stub works for tests; production wants a log or blob store.
+///
+/// Methods are written as `fn(..) -> impl Future<..> + Send` rather than
+/// `async fn` so the returned future is `Send`-bounded: a production
+/// log/blob-store backend needs to `.await` these from a multi-threaded
+/// executor.
+pub trait Storage<T: Types> {
+    /// The error returned when a durability operation fails.
+    type Error: Error;
+
+    /// Durably persist `instance`'s current `time`.
+    ///
+    /// `instance` identifies which acceptor this record belongs to, so one
+    /// `Storage` can back every instance of a [`Log`](crate::apaxos::log::Log)
+    /// without different instances overwriting each other's record.
+    fn persist_time(&mut self, instance: T::InstanceId, time: T::Time) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Durably persist `instance`'s current `accepted` value.
+    fn persist_accepted(
+        &mut self,
+        instance: T::InstanceId,
+        accepted: Accepted<T>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Load the persisted `time` and `accepted` value of `instance`, used to
+    /// rebuild an [`Acceptor`](crate::apaxos::acceptor::Acceptor) on startup.
+    ///
+    /// The returned pair must satisfy the [`Acceptor`](crate::apaxos::acceptor::Acceptor)
+    /// invariant that `time.greater_equal(&accepted.accept_time)`:
+    /// [`Acceptor::load`](crate::apaxos::acceptor::Acceptor::load) builds the
+    /// acceptor from it as is, without checking.
+    fn load(&self, instance: &T::InstanceId) -> impl Future<Output = Result<(T::Time, Option<Accepted<T>>), Self::Error>> + Send;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+
+    use crate::apaxos::acceptor::Acceptor;
+    use crate::apaxos::accepted::Accepted;
+    use crate::apaxos::storage::test_fixtures::TestTime;
+    use crate::apaxos::storage::test_fixtures::TestTypes;
+    use crate::apaxos::storage::Storage;
+
+    #[derive(Debug)]
+    struct AlwaysFails;
+
+    impl fmt::Display for AlwaysFails {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "storage unavailable")
+        }
+    }
+
+    impl std::error::Error for AlwaysFails {}
+
+    /// A [`Storage`] whose writes never succeed, used to assert that a
+    /// failed persist is never acknowledged and never observably mutates
+    /// the [`Acceptor`].
+    struct FailingStorage;
+
+    impl Storage<TestTypes> for FailingStorage {
+        type Error = AlwaysFails;
+
+        async fn persist_time(&mut self, _instance: u64, _time: TestTime) -> Result<(), AlwaysFails> {
+            Err(AlwaysFails)
+        }
+
+        async fn persist_accepted(&mut self, _instance: u64, _accepted: Accepted<TestTypes>) -> Result<(), AlwaysFails> {
+            Err(AlwaysFails)
+        }
+
+        async fn load(&self, _instance: &u64) -> Result<(TestTime, Option<Accepted<TestTypes>>), AlwaysFails> {
+            Ok((TestTime::default(), None))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_persist_does_not_acknowledge_or_advance_acceptor() {
+        let mut acceptor = Acceptor::<TestTypes>::default();
+        let mut storage = FailingStorage;
+
+        let result = acceptor.handle_phase1_request(&0u64, TestTime(5), &mut storage).await;
+
+        assert!(result.is_err());
+        assert_eq!(acceptor.time, TestTime::default());
+    }
+
+    /// A [`Storage`] whose `persist_time` succeeds but `persist_accepted`
+    /// always fails, used to assert that a partially-durable phase-2
+    /// write still leaves the in-memory [`Acceptor`] at least as far along
+    /// as what's actually durable.
+    struct TimeOnlyStorage;
+
+    impl Storage<TestTypes> for TimeOnlyStorage {
+        type Error = AlwaysFails;
+
+        async fn persist_time(&mut self, _instance: u64, _time: TestTime) -> Result<(), AlwaysFails> {
+            Ok(())
+        }
+
+        async fn persist_accepted(&mut self, _instance: u64, _accepted: Accepted<TestTypes>) -> Result<(), AlwaysFails> {
+            Err(AlwaysFails)
+        }
+
+        async fn load(&self, _instance: &u64) -> Result<(TestTime, Option<Accepted<TestTypes>>), AlwaysFails> {
+            Ok((TestTime::default(), None))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_phase2_failed_persist_accepted_still_advances_durable_time() {
+        use crate::apaxos::proposal::Proposal;
+
+        let mut acceptor = Acceptor::<TestTypes>::default();
+        let mut storage = TimeOnlyStorage;
+
+        let result = acceptor
+            .handle_phase2_request(&0u64, TestTime(5), Proposal::new(7u64), &mut storage)
+            .await;
+
+        assert!(result.is_err());
+        // `persist_time` durably recorded `TestTime(5)`, so the in-memory
+        // acceptor must not be left behind it, even though `accepted` never
+        // became durable.
+        assert_eq!(acceptor.time, TestTime(5));
+        assert!(acceptor.accepted.is_none());
+    }
+}
@@ -0,0 +1,25 @@
+//! Shared `Types` stub used across this module's unit tests, so
+//! `storage.rs`, `log.rs`, and `learner.rs` don't each redefine their own
+//! copy of the same `TestTime`/`TestTypes` fixture.
+
+use crate::apaxos::greater_equal::GreaterEqual;
+use crate::Types;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub(crate) struct TestTime(pub(crate) u64);
+
+impl GreaterEqual for TestTime {
+    fn greater_equal(&self, other: &Self) -> bool {
+        self.0 >= other.0
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TestTypes;
+
+impl Types for TestTypes {
+    type Time = TestTime;
+    type Part = u64;
+    type InstanceId = u64;
+    type NodeId = u64;
+}